@@ -1,20 +1,71 @@
 use crate::client::RpcClient;
 use kaspa_consensus_core::network::{NetworkId, NetworkType};
 use kaspa_python_macros::py_async;
-use kaspa_wrpc_client::{Resolver as NativeResolver, WrpcEncoding};
+use kaspa_wrpc_client::{client::ConnectOptions, KaspaRpcClient, Resolver as NativeResolver, WrpcEncoding};
 use pyo3::exceptions::PyException;
 use pyo3::prelude::*;
-use std::{str::FromStr, sync::Arc};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Rolling per-endpoint health score used by [`Resolver::connect_with_failover`]
+/// to rank candidates. `latency_ms` and `failure_rate` are EMAs, so scores
+/// recover once an endpoint starts succeeding again.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct EndpointStats {
+    /// EMA of the connect round-trip latency, in milliseconds.
+    pub latency_ms: Option<u64>,
+    /// EMA of the failure rate, in `[0.0, 1.0]`.
+    pub failure_rate: f64,
+    /// Lifetime (non-decaying) counters, exposed for observability only.
+    pub successes: u64,
+    pub failures: u64,
+    pub last_error: Option<String>,
+}
+
+impl EndpointStats {
+    /// Weight applied to a fresh sample when folding it into a rolling average.
+    const EMA_WEIGHT: f64 = 0.25;
+
+    fn ema(previous: f64, sample: f64) -> f64 {
+        (1.0 - Self::EMA_WEIGHT) * previous + Self::EMA_WEIGHT * sample
+    }
+
+    fn record_success(&mut self, latency_ms: u64) {
+        self.latency_ms = Some(match self.latency_ms {
+            Some(previous) => Self::ema(previous as f64, latency_ms as f64) as u64,
+            None => latency_ms,
+        });
+        self.failure_rate = Self::ema(self.failure_rate, 0.0);
+        self.successes += 1;
+        self.last_error = None;
+    }
+
+    fn record_failure(&mut self, error: String) {
+        self.failure_rate = Self::ema(self.failure_rate, 1.0);
+        self.failures += 1;
+        self.last_error = Some(error);
+    }
+
+    /// Lower is better: healthy, low-latency endpoints sort first.
+    fn rank_key(&self) -> (u64, u64) {
+        ((self.failure_rate * 1_000_000.0) as u64, self.latency_ms.unwrap_or(u64::MAX))
+    }
+}
 
 #[derive(Debug, Clone)]
 #[pyclass]
 pub struct Resolver {
     resolver: NativeResolver,
+    stats: Arc<Mutex<HashMap<String, EndpointStats>>>,
 }
 
 impl Resolver {
     pub fn new(resolver: NativeResolver) -> Self {
-        Self { resolver }
+        Self { resolver, stats: Default::default() }
     }
 }
 
@@ -24,9 +75,12 @@ impl Resolver {
     pub fn ctor(urls: Option<Vec<String>>, tls: Option<bool>) -> PyResult<Resolver> {
         let tls = tls.unwrap_or(false);
         if let Some(urls) = urls {
-            Ok(Self { resolver: NativeResolver::new(Some(urls.into_iter().map(|url| Arc::new(url)).collect::<Vec<_>>()), tls) })
+            Ok(Self {
+                resolver: NativeResolver::new(Some(urls.into_iter().map(|url| Arc::new(url)).collect::<Vec<_>>()), tls),
+                stats: Default::default(),
+            })
         } else {
-            Ok(Self { resolver: NativeResolver::default() })
+            Ok(Self { resolver: NativeResolver::default(), stats: Default::default() })
         }
     }
 }
@@ -69,6 +123,75 @@ impl Resolver {
         client.connect(py, None, None, None, None, None)?;
         Ok(client)
     }
+
+    /// Probe every candidate endpoint for `encoding`/`network`, awaiting a
+    /// confirmed connection to measure real round-trip latency, and fold the
+    /// result into the endpoint's rolling health score.
+    fn probe_nodes(&self, py: Python, encoding: String, network: String, network_suffix: Option<u32>) -> PyResult<Py<PyAny>> {
+        let encoding = WrpcEncoding::from_str(encoding.as_str()).unwrap();
+        let network_id = into_network_id(&network, network_suffix)?;
+        let urls = self.urls();
+        let stats = self.stats.clone();
+
+        py_async! {py, async move {
+            for url in urls {
+                match probe_connect(&url, encoding, network_id).await {
+                    Ok(latency) => stats.lock().unwrap().entry(url).or_default().record_success(latency.as_millis() as u64),
+                    Err(error) => stats.lock().unwrap().entry(url).or_default().record_failure(error.to_string()),
+                }
+            }
+            Ok(())
+        }}
+    }
+
+    /// Like [`Resolver::connect`], but prefers the best-ranked endpoint
+    /// recorded by [`Resolver::probe_nodes`], failing over to the next one on
+    /// a confirmed connection failure. Falls back to [`Resolver::connect`] if
+    /// every scored endpoint fails.
+    fn connect_with_failover(&self, py: Python, encoding: String, network: String, network_suffix: Option<u32>) -> PyResult<Py<PyAny>> {
+        let encoding_enum = WrpcEncoding::from_str(encoding.as_str()).unwrap();
+        let network_id = into_network_id(&network, network_suffix)?;
+        let resolver = self.clone();
+        let urls = self.ranked_urls();
+        let stats = self.stats.clone();
+
+        py_async! {py, async move {
+            for url in urls {
+                match probe_connect(&url, encoding_enum, network_id).await {
+                    Ok(latency) => {
+                        stats.lock().unwrap().entry(url.clone()).or_default().record_success(latency.as_millis() as u64);
+                        return Python::with_gil(|py| {
+                            let client = RpcClient::new(Some(resolver.clone()), Some(url), Some(encoding_enum), Some(network_id))?;
+                            client.connect(py, None, None, None, None, None)?;
+                            Ok(client.into_py(py))
+                        });
+                    }
+                    Err(error) => {
+                        stats.lock().unwrap().entry(url).or_default().record_failure(error.to_string());
+                    }
+                }
+            }
+
+            Python::with_gil(|py| Ok(resolver.connect(py, encoding, network, network_suffix)?.into_py(py)))
+        }}
+    }
+
+    /// The current per-endpoint latency/failure health scores, keyed by URL.
+    fn node_stats(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let stats = self.stats.lock().unwrap().clone();
+        Ok(serde_pyobject::to_pyobject(py, &stats).unwrap().to_object(py))
+    }
+}
+
+impl Resolver {
+    /// Candidate URLs ordered best-to-worst by [`EndpointStats::rank_key`].
+    /// Endpoints without any recorded data yet rank as if healthy.
+    fn ranked_urls(&self) -> Vec<String> {
+        let stats = self.stats.lock().unwrap();
+        let mut urls = self.urls();
+        urls.sort_by_key(|url| stats.get(url).map(EndpointStats::rank_key).unwrap_or((0, u64::MAX)));
+        urls
+    }
 }
 
 impl From<Resolver> for NativeResolver {
@@ -79,10 +202,27 @@ impl From<Resolver> for NativeResolver {
 
 impl From<NativeResolver> for Resolver {
     fn from(resolver: NativeResolver) -> Self {
-        Self { resolver }
+        Self { resolver, stats: Default::default() }
     }
 }
 
+/// Open a throwaway connection to `url` and wait for it to actually establish,
+/// returning the round-trip duration. The probe connection is disconnected
+/// before returning, so this never leaves a live connection behind.
+async fn probe_connect(url: &str, encoding: WrpcEncoding, network_id: NetworkId) -> Result<Duration, PyErr> {
+    let client = KaspaRpcClient::new(encoding, Some(url), None, Some(network_id), None).map_err(|err| PyErr::new::<PyException, _>(err.to_string()))?;
+
+    let started = Instant::now();
+    client
+        .connect(Some(ConnectOptions { block_async_connect: true, ..Default::default() }))
+        .await
+        .map_err(|err| PyErr::new::<PyException, _>(err.to_string()))?;
+    let elapsed = started.elapsed();
+
+    client.disconnect().await.map_err(|err| PyErr::new::<PyException, _>(err.to_string()))?;
+    Ok(elapsed)
+}
+
 pub fn into_network_id(network: &str, network_suffix: Option<u32>) -> Result<NetworkId, PyErr> {
     let network_type = NetworkType::from_str(network).map_err(|_| PyErr::new::<PyException, _>("Invalid network type"))?;
     NetworkId::try_from(network_type).or_else(|_| {
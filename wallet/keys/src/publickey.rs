@@ -19,9 +19,31 @@
 
 use crate::imports::*;
 
+use hmac::{Hmac, Mac};
 use kaspa_consensus_core::network::NetworkType;
 use ripemd::{Digest, Ripemd160};
-use sha2::Sha256;
+use sha2::{Sha256, Sha512};
+
+/// MuSig2 key-aggregation domain separation tags (BIP327-style tagged hashes).
+const MUSIG2_KEYAGG_LIST_TAG: &[u8] = b"KeyAgg list";
+const MUSIG2_KEYAGG_COEFF_TAG: &[u8] = b"KeyAgg coefficient";
+
+/// BIP340-style tagged hash: `sha256(sha256(tag) || sha256(tag) || msg)`.
+fn tagged_hash(tag: &[u8], msg: &[u8]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag);
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    hasher.update(msg);
+    hasher.finalize().into()
+}
+
+/// The scalar `1`, used for the MuSig2 second-distinct-key coefficient optimization.
+fn one_scalar() -> secp256k1::Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[31] = 1;
+    secp256k1::Scalar::from_be_bytes(bytes).expect("1 is a valid secp256k1 scalar")
+}
 
 /// Data structure that envelopes a PublicKey.
 /// Only supports Schnorr-based addresses.
@@ -61,6 +83,15 @@ impl PublicKey {
         self.to_address(network.try_into()?)
     }
 
+    /// MuSig2 key aggregation: combine `keys` into a single aggregate [`PublicKey`],
+    /// e.g. to derive an n-of-n treasury [`Address`] without an on-chain multisig
+    /// script.
+    #[wasm_bindgen(js_name = aggregate)]
+    pub fn aggregate_js(keys: &PublicKeyArrayT) -> Result<PublicKey> {
+        let keys: Vec<secp256k1::PublicKey> = keys.try_into()?;
+        Self::aggregate(&keys)
+    }
+
     /// Get `ECDSA` [`Address`] of this PublicKey.
     /// Receives a [`NetworkType`] to determine the prefix of the address.
     /// JavaScript: `let address = publicKey.toAddress(NetworkType.MAINNET);`.
@@ -117,6 +148,13 @@ impl PublicKey {
     pub fn to_address_ecdsa_py(&self, network: &str) -> Result<Address> {
         self.to_address_ecdsa(NetworkType::from_str(network)?)
     }
+
+    #[staticmethod]
+    #[pyo3(name = "aggregate")]
+    pub fn aggregate_py(keys: Vec<PublicKey>) -> Result<PublicKey> {
+        let keys = keys.iter().map(secp256k1::PublicKey::try_from).collect::<Result<Vec<_>>>()?;
+        Self::aggregate(&keys)
+    }
 }
 
 impl PublicKey {
@@ -137,6 +175,49 @@ impl PublicKey {
             Err(Error::InvalidXOnlyPublicKeyForECDSA)
         }
     }
+
+    /// MuSig2 key aggregation (`KeyAgg`): sorts `keys` lexicographically by their
+    /// compressed serialization, computes the aggregate challenge
+    /// `L = sha256(P_1 || P_2 || ... || P_n)` (tagged with `"KeyAgg list"`), derives
+    /// per-key coefficients `a_i = sha256(L || P_i)` (tagged with
+    /// `"KeyAgg coefficient"`, with the standard optimization that the second
+    /// *distinct* key in the sorted list gets coefficient `1`), and returns the
+    /// aggregate point `Q = sum(a_i * P_i)`. The result can be turned into a
+    /// single-signer-looking Schnorr [`Address`] via [`PublicKey::to_address`],
+    /// enabling n-of-n treasury addresses without an on-chain multisig script.
+    pub fn aggregate(keys: &[secp256k1::PublicKey]) -> Result<PublicKey> {
+        if keys.is_empty() {
+            return Err(Error::custom("at least one public key is required for aggregation"));
+        }
+
+        let mut sorted = keys.to_vec();
+        sorted.sort_by_key(|key| key.serialize());
+
+        let mut list_preimage = Vec::with_capacity(sorted.len() * 33);
+        sorted.iter().for_each(|key| list_preimage.extend_from_slice(&key.serialize()));
+        let aggregate_challenge = tagged_hash(MUSIG2_KEYAGG_LIST_TAG, &list_preimage);
+        let second_distinct_key = sorted.iter().find(|key| key.serialize() != sorted[0].serialize());
+
+        let secp = secp256k1::Secp256k1::new();
+        let mut aggregate_point: Option<secp256k1::PublicKey> = None;
+        for key in &sorted {
+            let coefficient = if Some(key) == second_distinct_key {
+                one_scalar()
+            } else {
+                let mut preimage = aggregate_challenge.to_vec();
+                preimage.extend_from_slice(&key.serialize());
+                let coefficient_hash = tagged_hash(MUSIG2_KEYAGG_COEFF_TAG, &preimage);
+                secp256k1::Scalar::from_be_bytes(coefficient_hash).map_err(|_| Error::custom("key aggregation coefficient is out of range"))?
+            };
+            let term = key.mul_tweak(&secp, &coefficient)?;
+            aggregate_point = Some(match aggregate_point {
+                None => term,
+                Some(acc) => acc.combine(&term)?,
+            });
+        }
+
+        Ok(aggregate_point.expect("at least one key was checked above").into())
+    }
 }
 
 impl std::fmt::Display for PublicKey {
@@ -341,3 +422,612 @@ impl TryFrom<JsValue> for XOnlyPublicKey {
         }
     }
 }
+
+// ---
+// Silent payments (reusable stealth payment codes)
+//
+// Implements the BIP351/BIP352-style ECDH construction: a recipient publishes a
+// static `PaymentCode` (a scan/spend public key pair) and senders derive a fresh,
+// unlinkable one-time output key for every payment without any further
+// interaction with the recipient.
+// ---
+
+/// A reusable silent payment code: a publishable pair of scan/spend public keys
+/// from which a sender derives a fresh, unlinkable one-time [`Address`] per payment.
+/// [`PaymentCode::random`] also retains the secret keys needed for [`PaymentCode::scan`].
+/// @category Wallet SDK
+#[derive(Clone, Debug, CastFromJs)]
+#[cfg_attr(feature = "py-sdk", pyclass)]
+#[wasm_bindgen(js_name = PaymentCode)]
+pub struct PaymentCode {
+    #[wasm_bindgen(skip)]
+    pub scan_public_key: secp256k1::PublicKey,
+    #[wasm_bindgen(skip)]
+    pub spend_public_key: secp256k1::PublicKey,
+    #[wasm_bindgen(skip)]
+    pub scan_secret_key: Option<secp256k1::SecretKey>,
+    #[wasm_bindgen(skip)]
+    pub spend_secret_key: Option<secp256k1::SecretKey>,
+}
+
+/// The output index and recovered one-time spend private key from a successful
+/// [`PaymentCode::scan`] match.
+/// @category Wallet SDK
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "py-sdk", pyclass)]
+#[wasm_bindgen(js_name = SilentPaymentMatch)]
+pub struct SilentPaymentMatch {
+    output_index: u32,
+    spend_secret_key: secp256k1::SecretKey,
+}
+
+#[wasm_bindgen(js_class = SilentPaymentMatch)]
+impl SilentPaymentMatch {
+    #[wasm_bindgen(getter, js_name = outputIndex)]
+    pub fn output_index(&self) -> u32 {
+        self.output_index
+    }
+
+    #[wasm_bindgen(getter, js_name = spendPrivateKey)]
+    pub fn spend_private_key(&self) -> HexString {
+        self.spend_secret_key.secret_bytes().as_slice().to_hex().into()
+    }
+}
+
+#[cfg(feature = "py-sdk")]
+#[pymethods]
+impl SilentPaymentMatch {
+    #[getter]
+    #[pyo3(name = "output_index")]
+    pub fn output_index_py(&self) -> u32 {
+        self.output_index
+    }
+
+    #[getter]
+    #[pyo3(name = "spend_private_key")]
+    pub fn spend_private_key_py(&self) -> String {
+        self.spend_secret_key.secret_bytes().as_slice().to_hex()
+    }
+}
+
+// PY-NOTE: WASM specific fn implementations
+#[wasm_bindgen(js_class = PaymentCode)]
+impl PaymentCode {
+    /// Create a new public-only [`PaymentCode`] from hex-encoded scan/spend public keys.
+    /// Used by a sender who has received a recipient's payment code out-of-band.
+    #[wasm_bindgen(constructor)]
+    pub fn try_new(scan_public_key: &str, spend_public_key: &str) -> Result<PaymentCode> {
+        Ok(Self {
+            scan_public_key: secp256k1::PublicKey::from_str(scan_public_key)?,
+            spend_public_key: secp256k1::PublicKey::from_str(spend_public_key)?,
+            scan_secret_key: None,
+            spend_secret_key: None,
+        })
+    }
+
+    /// Generate a brand-new [`PaymentCode`], including the scan/spend secret keys
+    /// required to later [`PaymentCode::scan`] incoming transactions.
+    pub fn random() -> PaymentCode {
+        let secp = secp256k1::Secp256k1::new();
+        let (scan_secret_key, scan_public_key) = secp.generate_keypair(&mut rand::thread_rng());
+        let (spend_secret_key, spend_public_key) = secp.generate_keypair(&mut rand::thread_rng());
+        Self { scan_public_key, spend_public_key, scan_secret_key: Some(scan_secret_key), spend_secret_key: Some(spend_secret_key) }
+    }
+
+    #[wasm_bindgen(js_name = "toString")]
+    pub fn to_string_impl(&self) -> String {
+        format!("{}{}", self.scan_public_key, self.spend_public_key)
+    }
+
+    #[wasm_bindgen(getter, js_name = scanPublicKey)]
+    pub fn scan_public_key_js(&self) -> PublicKey {
+        self.scan_public_key.into()
+    }
+
+    #[wasm_bindgen(getter, js_name = spendPublicKey)]
+    pub fn spend_public_key_js(&self) -> PublicKey {
+        self.spend_public_key.into()
+    }
+
+    /// Sender side: sum the secret keys of the inputs being spent into a scalar `a`,
+    /// derive the ECDH shared secret with the recipient's scan key, and produce
+    /// `count` one-time output [`Address`]es starting at output index `start_index`.
+    #[wasm_bindgen(js_name = createOutputs)]
+    pub fn create_outputs_js(&self, input_secret_keys: Vec<String>, start_index: u32, count: u32, network: &NetworkTypeT) -> Result<Vec<Address>> {
+        let input_secret_keys =
+            input_secret_keys.iter().map(|key| secp256k1::SecretKey::from_str(key)).collect::<std::result::Result<Vec<_>, _>>()?;
+        self.create_outputs(&input_secret_keys, start_index, count, network.try_into()?)
+    }
+
+    /// Recipient side: recompute the ECDH shared secret from the sender's summed
+    /// input public key `A` and try to match each candidate output against the
+    /// derived one-time keys `P_0, P_1, ...` up to `max_outputs`.
+    pub fn scan(&self, input_public_key_sum: &str, outputs: Vec<String>, max_outputs: u32) -> Result<Vec<SilentPaymentMatch>> {
+        let input_public_key_sum = secp256k1::PublicKey::from_str(input_public_key_sum)?;
+        let outputs = outputs.iter().map(|output| secp256k1::XOnlyPublicKey::from_str(output)).collect::<std::result::Result<Vec<_>, _>>()?;
+        self.scan_impl(&input_public_key_sum, &outputs, max_outputs)
+    }
+}
+
+// PY-NOTE: Python specific fn implementations
+#[cfg(feature = "py-sdk")]
+#[pymethods]
+impl PaymentCode {
+    #[new]
+    pub fn try_new_py(scan_public_key: &str, spend_public_key: &str) -> Result<PaymentCode> {
+        Self::try_new(scan_public_key, spend_public_key)
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "random")]
+    pub fn random_py() -> PaymentCode {
+        Self::random()
+    }
+
+    #[pyo3(name = "to_string")]
+    pub fn to_string_impl_py(&self) -> String {
+        self.to_string_impl()
+    }
+
+    #[getter]
+    #[pyo3(name = "scan_public_key")]
+    pub fn scan_public_key_py(&self) -> PublicKey {
+        self.scan_public_key.into()
+    }
+
+    #[getter]
+    #[pyo3(name = "spend_public_key")]
+    pub fn spend_public_key_py(&self) -> PublicKey {
+        self.spend_public_key.into()
+    }
+
+    #[pyo3(name = "create_outputs")]
+    pub fn create_outputs_py(&self, input_secret_keys: Vec<String>, start_index: u32, count: u32, network: &str) -> Result<Vec<Address>> {
+        let input_secret_keys =
+            input_secret_keys.iter().map(|key| secp256k1::SecretKey::from_str(key)).collect::<std::result::Result<Vec<_>, _>>()?;
+        self.create_outputs(&input_secret_keys, start_index, count, NetworkType::from_str(network)?)
+    }
+
+    #[pyo3(name = "scan")]
+    pub fn scan_py(&self, input_public_key_sum: &str, outputs: Vec<String>, max_outputs: u32) -> Result<Vec<SilentPaymentMatch>> {
+        self.scan(input_public_key_sum, outputs, max_outputs)
+    }
+}
+
+impl PaymentCode {
+    /// `ecdh = a * B_scan`, serialized as a compressed 33-byte point.
+    fn ecdh_secret(scan_public_key: &secp256k1::PublicKey, scalar: &secp256k1::Scalar) -> Result<[u8; 33]> {
+        let secp = secp256k1::Secp256k1::new();
+        let ecdh = scan_public_key.mul_tweak(&secp, scalar)?;
+        Ok(ecdh.serialize())
+    }
+
+    /// `t_k = sha256(serialize(ecdh) || ser32(k))`
+    fn output_tweak(ecdh: &[u8; 33], k: u32) -> Result<secp256k1::Scalar> {
+        let mut hasher = Sha256::new();
+        hasher.update(ecdh);
+        hasher.update(k.to_be_bytes());
+        let tweak: [u8; 32] = hasher.finalize().into();
+        secp256k1::Scalar::from_be_bytes(tweak).map_err(|_| Error::custom("silent payment tweak is out of range"))
+    }
+
+    pub fn create_outputs(
+        &self,
+        input_secret_keys: &[secp256k1::SecretKey],
+        start_index: u32,
+        count: u32,
+        network_type: NetworkType,
+    ) -> Result<Vec<Address>> {
+        let secp = secp256k1::Secp256k1::new();
+        let mut input_secret_keys = input_secret_keys.iter();
+        let first = input_secret_keys.next().ok_or(Error::custom("at least one input secret key is required"))?;
+        let a = input_secret_keys.try_fold(*first, |acc, key| acc.add_tweak(&secp256k1::Scalar::from(*key)))?;
+        let scalar = secp256k1::Scalar::from(a);
+        let ecdh = Self::ecdh_secret(&self.scan_public_key, &scalar)?;
+        let end_index = start_index.checked_add(count).ok_or_else(|| Error::custom("start_index + count overflows u32"))?;
+
+        (start_index..end_index)
+            .map(|k| {
+                let tweak = Self::output_tweak(&ecdh, k)?;
+                let output_key = self.spend_public_key.add_exp_tweak(&secp, &tweak)?;
+                let public_key: PublicKey = (&output_key).into();
+                public_key.to_address(network_type)
+            })
+            .collect()
+    }
+
+    fn scan_impl(
+        &self,
+        input_public_key_sum: &secp256k1::PublicKey,
+        outputs: &[secp256k1::XOnlyPublicKey],
+        max_outputs: u32,
+    ) -> Result<Vec<SilentPaymentMatch>> {
+        let scan_secret_key = self.scan_secret_key.ok_or(Error::custom("scan secret key is required to scan for silent payments"))?;
+        let spend_secret_key = self.spend_secret_key.ok_or(Error::custom("spend secret key is required to scan for silent payments"))?;
+
+        let secp = secp256k1::Secp256k1::new();
+        let ecdh = {
+            let scalar = secp256k1::Scalar::from(scan_secret_key);
+            input_public_key_sum.mul_tweak(&secp, &scalar)?.serialize()
+        };
+
+        let mut matches = Vec::new();
+        for k in 0..max_outputs {
+            let tweak = Self::output_tweak(&ecdh, k)?;
+            let candidate = self.spend_public_key.add_exp_tweak(&secp, &tweak)?;
+            let (candidate_xonly, _) = candidate.x_only_public_key();
+            if let Some(output_index) = outputs.iter().position(|output| *output == candidate_xonly) {
+                let spend_secret_key = spend_secret_key.add_tweak(&tweak)?;
+                matches.push(SilentPaymentMatch { output_index: output_index as u32, spend_secret_key });
+            }
+        }
+        Ok(matches)
+    }
+}
+
+impl std::fmt::Display for PaymentCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_string_impl())
+    }
+}
+
+// ---
+// Watch-only BIP32 extended public keys
+// ---
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Index boundary separating normal (non-hardened) child indices from hardened
+/// ones. Hardened derivation requires the parent private key and is therefore
+/// unsupported by [`ExtendedPublicKey`], which only ever holds public material.
+const HARDENED_CHILD_INDEX_BOUNDARY: u32 = 1 << 31;
+
+/// BIP32-style version bytes per [`NetworkType`], used when serializing an
+/// [`ExtendedPublicKey`] to (and parsing it from) its base58check string form.
+/// Mirrors the well-known Bitcoin `xpub`/`tpub` prefixes pending a
+/// kaspa-specific BIP32 version registry.
+fn extended_public_key_version(network_type: NetworkType) -> [u8; 4] {
+    match network_type {
+        NetworkType::Mainnet => [0x04, 0x88, 0xb2, 0x1e], // xpub
+        NetworkType::Testnet => [0x04, 0x35, 0x87, 0xcf], // tpub
+        NetworkType::Devnet => [0x04, 0x35, 0x87, 0xd0],
+        NetworkType::Simnet => [0x04, 0x35, 0x87, 0xd1],
+    }
+}
+
+/// The inverse of [`extended_public_key_version`]; rejects any prefix that
+/// doesn't correspond to a known network.
+fn network_type_from_extended_public_key_version(version: &[u8]) -> Result<NetworkType> {
+    match version {
+        [0x04, 0x88, 0xb2, 0x1e] => Ok(NetworkType::Mainnet),
+        [0x04, 0x35, 0x87, 0xcf] => Ok(NetworkType::Testnet),
+        [0x04, 0x35, 0x87, 0xd0] => Ok(NetworkType::Devnet),
+        [0x04, 0x35, 0x87, 0xd1] => Ok(NetworkType::Simnet),
+        _ => Err(Error::custom("unrecognized extended public key version prefix")),
+    }
+}
+
+/// A watch-only BIP32 extended public key: a [`PublicKey`] paired with the chain
+/// code and derivation metadata needed to derive non-hardened child public keys
+/// (CKD_pub) without access to the corresponding private key. This enables
+/// address generation for accounting/monitoring wallets that should never hold
+/// spending authority.
+/// @category Wallet SDK
+#[derive(Clone, Debug, CastFromJs)]
+#[cfg_attr(feature = "py-sdk", pyclass)]
+#[wasm_bindgen(js_name = ExtendedPublicKey)]
+pub struct ExtendedPublicKey {
+    #[wasm_bindgen(skip)]
+    pub public_key: secp256k1::PublicKey,
+    #[wasm_bindgen(skip)]
+    pub chain_code: [u8; 32],
+    pub depth: u8,
+    #[wasm_bindgen(skip)]
+    pub parent_fingerprint: [u8; 4],
+    pub child_number: u32,
+    #[wasm_bindgen(skip)]
+    pub network_type: NetworkType,
+}
+
+// PY-NOTE: WASM specific fn implementations
+#[wasm_bindgen(js_class = ExtendedPublicKey)]
+impl ExtendedPublicKey {
+    /// Create a root [`ExtendedPublicKey`] from a [`PublicKey`] and its 32-byte
+    /// hex-encoded chain code, as produced alongside a master or account key.
+    #[wasm_bindgen(constructor)]
+    pub fn try_new(public_key: &PublicKey, chain_code: &str, network: &NetworkTypeT) -> Result<ExtendedPublicKey> {
+        Ok(Self::new_root(public_key.try_into()?, decode_hex_32(chain_code)?, network.try_into()?))
+    }
+
+    #[wasm_bindgen(js_name = "toString")]
+    pub fn to_string_impl(&self) -> String {
+        let data = self.to_bytes();
+        let checksum = Sha256::digest(Sha256::digest(data));
+        let mut payload = data.to_vec();
+        payload.extend_from_slice(&checksum[..4]);
+        bs58::encode(payload).into_string()
+    }
+
+    #[wasm_bindgen(js_name = fromString)]
+    pub fn from_str_js(xpub: &str) -> Result<ExtendedPublicKey> {
+        Self::from_xpub_str(xpub)
+    }
+
+    #[wasm_bindgen(getter, js_name = publicKey)]
+    pub fn public_key_js(&self) -> PublicKey {
+        self.public_key.into()
+    }
+
+    #[wasm_bindgen(getter, js_name = chainCode)]
+    pub fn chain_code_js(&self) -> HexString {
+        self.chain_code.as_slice().to_hex().into()
+    }
+
+    #[wasm_bindgen(getter, js_name = parentFingerprint)]
+    pub fn parent_fingerprint_js(&self) -> HexString {
+        self.parent_fingerprint.as_slice().to_hex().into()
+    }
+
+    /// Derive the non-hardened child at `index` (must be `< 2^31`).
+    #[wasm_bindgen(js_name = deriveChild)]
+    pub fn derive_child_js(&self, index: u32) -> Result<ExtendedPublicKey> {
+        self.derive_child(index)
+    }
+
+    /// Derive a descendant along a `"m/0/1/5"`-style path. All indices in the
+    /// path must be non-hardened.
+    #[wasm_bindgen(js_name = derivePath)]
+    pub fn derive_path_js(&self, path: &str) -> Result<ExtendedPublicKey> {
+        self.derive_path(path)
+    }
+}
+
+// PY-NOTE: Python specific fn implementations
+#[cfg(feature = "py-sdk")]
+#[pymethods]
+impl ExtendedPublicKey {
+    #[new]
+    pub fn try_new_py(public_key: &PublicKey, chain_code: &str, network: &str) -> Result<ExtendedPublicKey> {
+        Ok(Self::new_root(public_key.try_into()?, decode_hex_32(chain_code)?, NetworkType::from_str(network)?))
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "from_string")]
+    pub fn from_str_py(xpub: &str) -> Result<ExtendedPublicKey> {
+        Self::from_xpub_str(xpub)
+    }
+
+    #[pyo3(name = "to_string")]
+    pub fn to_string_impl_py(&self) -> String {
+        self.to_string_impl()
+    }
+
+    #[getter]
+    #[pyo3(name = "public_key")]
+    pub fn public_key_py(&self) -> PublicKey {
+        self.public_key.into()
+    }
+
+    #[getter]
+    #[pyo3(name = "chain_code")]
+    pub fn chain_code_py(&self) -> String {
+        self.chain_code.as_slice().to_hex()
+    }
+
+    #[getter]
+    #[pyo3(name = "parent_fingerprint")]
+    pub fn parent_fingerprint_py(&self) -> String {
+        self.parent_fingerprint.as_slice().to_hex()
+    }
+
+    #[pyo3(name = "derive_child")]
+    pub fn derive_child_py(&self, index: u32) -> Result<ExtendedPublicKey> {
+        self.derive_child(index)
+    }
+
+    #[pyo3(name = "derive_path")]
+    pub fn derive_path_py(&self, path: &str) -> Result<ExtendedPublicKey> {
+        self.derive_path(path)
+    }
+}
+
+impl ExtendedPublicKey {
+    fn new_root(public_key: secp256k1::PublicKey, chain_code: [u8; 32], network_type: NetworkType) -> Self {
+        Self { public_key, chain_code, depth: 0, parent_fingerprint: [0; 4], child_number: 0, network_type }
+    }
+
+    /// Non-hardened public child key derivation (CKD_pub):
+    /// `I = HMAC-SHA512(key = chain_code, data = serP(K_par) || ser32(i))`,
+    /// `K_i = K_par + I_L*G`, child chain code `= I_R`.
+    pub fn derive_child(&self, index: u32) -> Result<ExtendedPublicKey> {
+        if index >= HARDENED_CHILD_INDEX_BOUNDARY {
+            return Err(Error::custom("hardened child derivation is not supported for an ExtendedPublicKey"));
+        }
+
+        let mut mac = HmacSha512::new_from_slice(&self.chain_code).expect("HMAC accepts a key of any size");
+        mac.update(&self.public_key.serialize());
+        mac.update(&index.to_be_bytes());
+        let i = mac.finalize().into_bytes();
+        let (i_left, i_right) = i.split_at(32);
+
+        let tweak = secp256k1::Scalar::from_be_bytes(i_left.try_into().unwrap())
+            .map_err(|_| Error::custom("derived child key tweak is out of range"))?;
+        let secp = secp256k1::Secp256k1::new();
+        let child_public_key = self.public_key.add_exp_tweak(&secp, &tweak)?;
+
+        Ok(Self {
+            public_key: child_public_key,
+            chain_code: i_right.try_into().unwrap(),
+            depth: self.depth + 1,
+            parent_fingerprint: self.fingerprint_bytes(),
+            child_number: index,
+            network_type: self.network_type,
+        })
+    }
+
+    /// Derive a descendant along a `"m/0/1/5"`-style path (non-hardened only).
+    pub fn derive_path(&self, path: &str) -> Result<ExtendedPublicKey> {
+        let mut segments = path.split('/');
+        match segments.next() {
+            Some("m") | Some("") => {}
+            _ => return Err(Error::custom("derivation path must start with 'm'")),
+        }
+
+        segments.try_fold(self.clone(), |key, segment| {
+            if segment.is_empty() {
+                return Ok(key);
+            }
+            if segment.ends_with('\'') || segment.ends_with('h') || segment.ends_with('H') {
+                return Err(Error::custom("hardened path segments are not supported for an ExtendedPublicKey"));
+            }
+            let index = segment.parse::<u32>().map_err(|_| Error::custom("invalid derivation path segment"))?;
+            key.derive_child(index)
+        })
+    }
+
+    fn fingerprint_bytes(&self) -> [u8; 4] {
+        let digest = Ripemd160::digest(Sha256::digest(self.public_key.serialize().as_slice()));
+        digest[..4].try_into().unwrap()
+    }
+
+    fn to_bytes(&self) -> [u8; 78] {
+        let mut bytes = [0u8; 78];
+        bytes[0..4].copy_from_slice(&extended_public_key_version(self.network_type));
+        bytes[4] = self.depth;
+        bytes[5..9].copy_from_slice(&self.parent_fingerprint);
+        bytes[9..13].copy_from_slice(&self.child_number.to_be_bytes());
+        bytes[13..45].copy_from_slice(&self.chain_code);
+        bytes[45..78].copy_from_slice(&self.public_key.serialize());
+        bytes
+    }
+
+    fn from_xpub_str(xpub: &str) -> Result<ExtendedPublicKey> {
+        let payload = bs58::decode(xpub).into_vec().map_err(|_| Error::custom("invalid base58 extended public key"))?;
+        if payload.len() != 82 {
+            return Err(Error::custom("invalid extended public key length"));
+        }
+        let (data, checksum) = payload.split_at(78);
+        let expected_checksum = Sha256::digest(Sha256::digest(data));
+        if checksum != &expected_checksum[..4] {
+            return Err(Error::custom("invalid extended public key checksum"));
+        }
+        let network_type = network_type_from_extended_public_key_version(&data[0..4])?;
+
+        Ok(Self {
+            public_key: secp256k1::PublicKey::from_slice(&data[45..78])?,
+            chain_code: data[13..45].try_into().unwrap(),
+            depth: data[4],
+            parent_fingerprint: data[5..9].try_into().unwrap(),
+            child_number: u32::from_be_bytes(data[9..13].try_into().unwrap()),
+            network_type,
+        })
+    }
+}
+
+impl std::fmt::Display for ExtendedPublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_string_impl())
+    }
+}
+
+fn decode_hex_32(hex: &str) -> Result<[u8; 32]> {
+    if !hex.is_ascii() || hex.len() != 64 {
+        return Err(Error::custom("expected a 32-byte hex-encoded chain code"));
+    }
+    let hex = hex.as_bytes();
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            let byte = std::str::from_utf8(&hex[i..i + 2]).unwrap();
+            u8::from_str_radix(byte, 16).map_err(|_| Error::custom("invalid hex-encoded chain code"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(bytes.try_into().expect("exactly 32 bytes"))
+}
+
+#[cfg(test)]
+mod musig2_tests {
+    use super::*;
+
+    fn random_public_key() -> secp256k1::PublicKey {
+        let secp = secp256k1::Secp256k1::new();
+        secp.generate_keypair(&mut rand::thread_rng()).1
+    }
+
+    /// Reimplementation of [`PublicKey::aggregate`]'s key-agg formula used to
+    /// independently check its invariants (sorting, the second-distinct-key
+    /// coefficient optimization) rather than just re-running the same code.
+    fn expected_aggregate(sorted: &[secp256k1::PublicKey]) -> secp256k1::PublicKey {
+        let mut list_preimage = Vec::with_capacity(sorted.len() * 33);
+        sorted.iter().for_each(|key| list_preimage.extend_from_slice(&key.serialize()));
+        let aggregate_challenge = tagged_hash(MUSIG2_KEYAGG_LIST_TAG, &list_preimage);
+        let second_distinct_key = sorted.iter().find(|key| key.serialize() != sorted[0].serialize());
+
+        let secp = secp256k1::Secp256k1::new();
+        sorted
+            .iter()
+            .map(|key| {
+                let coefficient = if Some(key) == second_distinct_key {
+                    one_scalar()
+                } else {
+                    let mut preimage = aggregate_challenge.to_vec();
+                    preimage.extend_from_slice(&key.serialize());
+                    secp256k1::Scalar::from_be_bytes(tagged_hash(MUSIG2_KEYAGG_COEFF_TAG, &preimage)).unwrap()
+                };
+                key.mul_tweak(&secp, &coefficient).unwrap()
+            })
+            .reduce(|acc, term| acc.combine(&term).unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn aggregate_is_order_independent_and_sorts_lexicographically() {
+        let keys = [random_public_key(), random_public_key(), random_public_key()];
+        let mut sorted = keys;
+        sorted.sort_by_key(|key| key.serialize());
+
+        let forward = PublicKey::aggregate(&keys).unwrap();
+        let shuffled = PublicKey::aggregate(&[keys[2], keys[0], keys[1]]).unwrap();
+        assert_eq!(forward.to_string_impl(), shuffled.to_string_impl());
+        assert_eq!(forward.public_key.unwrap(), expected_aggregate(&sorted));
+    }
+
+    /// Hard-coded key-agg vector (two fixed, publicly reproducible private keys)
+    /// computed independently of this module, so a shared mistake in
+    /// `PublicKey::aggregate`'s tag constants, sort order, or coefficient rule
+    /// can't also reproduce itself in the expected value, unlike
+    /// [`expected_aggregate`] above.
+    #[test]
+    fn aggregate_matches_hardcoded_vector() {
+        #[rustfmt::skip]
+        let key1 = secp256k1::PublicKey::from_slice(&[
+            0x02, 0x31, 0xf9, 0xde, 0x86, 0x0d, 0x50, 0x8d, 0xeb, 0x4f, 0x97, 0x25, 0x41, 0x60, 0xd3, 0x5d,
+            0x2b, 0xfa, 0x85, 0xe9, 0xcb, 0x7d, 0xa1, 0x1c, 0x62, 0xf1, 0x8b, 0x57, 0x2d, 0x3b, 0x32, 0xd2, 0x04,
+        ])
+        .unwrap();
+        #[rustfmt::skip]
+        let key2 = secp256k1::PublicKey::from_slice(&[
+            0x02, 0xe3, 0x03, 0xb1, 0x03, 0x60, 0xf4, 0xe1, 0xdd, 0x66, 0xa1, 0x27, 0x0e, 0x43, 0x4e, 0x9c,
+            0xbc, 0xa8, 0x1f, 0x4d, 0xa0, 0xb7, 0xdf, 0x75, 0x37, 0x75, 0xe0, 0xf5, 0x1d, 0x29, 0x5d, 0x72, 0x3e,
+        ])
+        .unwrap();
+        #[rustfmt::skip]
+        let expected = secp256k1::PublicKey::from_slice(&[
+            0x02, 0x23, 0xa4, 0xb1, 0xa7, 0x8b, 0x6d, 0x03, 0x7a, 0x3e, 0x54, 0x4c, 0xf0, 0x2c, 0xbf, 0xca,
+            0xbc, 0xac, 0x08, 0x27, 0x62, 0x0e, 0x68, 0xc8, 0xd6, 0xc9, 0xee, 0x64, 0x2d, 0x8d, 0x1a, 0xcc, 0x77,
+        ])
+        .unwrap();
+
+        let aggregate = PublicKey::aggregate(&[key1, key2]).unwrap();
+        assert_eq!(aggregate.public_key.unwrap(), expected);
+    }
+
+    #[test]
+    fn aggregate_of_identical_keys_hashes_every_coefficient() {
+        let key = random_public_key();
+        let keys = [key, key, key];
+
+        let aggregate = PublicKey::aggregate(&keys).unwrap();
+        assert_eq!(aggregate.public_key.unwrap(), expected_aggregate(&keys));
+    }
+}